@@ -0,0 +1,273 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::Write;
+use anyhow::Result;
+use regex::Regex;
+
+/// Placeholder names (without the leading `$`) referenced by `en`, in first-appearance order.
+fn placeholders(en: &str, re: &Regex) -> Vec<String> {
+    let mut seen = Vec::new();
+    for m in re.find_iter(en) {
+        let name = m.as_str()[1..].to_string();
+        if !seen.contains(&name) {
+            seen.push(name);
+        }
+    }
+    seen
+}
+
+/// Rust keywords that a lowercase slug or placeholder name could collide with.
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in",
+    "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Turns an English sentence into a valid, lowercase `snake_case` function name.
+fn slug(en: &str) -> String {
+    let mut out = String::new();
+    let mut last_was_sep = true;
+    for c in en.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+            last_was_sep = false;
+        } else if !last_was_sep {
+            out.push('_');
+            last_was_sep = true;
+        }
+    }
+    let out = out.trim_matches('_').to_string();
+    if out.is_empty() || out.chars().next().unwrap().is_ascii_digit() || RUST_KEYWORDS.contains(&out.as_str()) {
+        format!("tr_{}", out)
+    } else {
+        out
+    }
+}
+
+/// Turns a placeholder name into a valid Rust identifier for use as a function parameter /
+/// `interpolate` argument binding. The placeholder regex (`\$[a-zA-Z0-9_-]+`) allows hyphens and
+/// leading digits that aren't valid in an identifier (`$user-name`, `$1`), so non-`[a-zA-Z0-9_]`
+/// characters are replaced with `_` and a `p_` prefix is added when the result would otherwise
+/// start with a digit, be empty or be a Rust keyword (`$type`). The original placeholder text is still used as the literal
+/// lookup key passed to `interpolate`, so this only affects the generated identifier.
+fn param_ident(name: &str) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    if out.is_empty() || out.chars().next().unwrap().is_ascii_digit() || RUST_KEYWORDS.contains(&out.as_str()) {
+        out.insert_str(0, "p_");
+    }
+    out
+}
+
+/// Turns a BCP-47-ish language code (`zh_cn`) into a `PascalCase` enum variant (`ZhCn`).
+fn lang_variant(lang: &str) -> String {
+    lang.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Emitted once per generated module. Does a single pass over `template`, matching each
+/// `$placeholder` as a whole token (longest run of identifier characters after `$`) before
+/// looking it up in `params`, so a placeholder whose name is a prefix of another's (`$count`
+/// vs `$countMax`) can't be partially matched by the wrong substitution.
+const INTERPOLATE_HELPER: &str = "\
+#[allow(dead_code)]
+fn interpolate(template: &str, params: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let bytes = template.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'$' {
+            let start = i + 1;
+            let mut end = start;
+            while end < bytes.len()
+                && (bytes[end].is_ascii_alphanumeric() || bytes[end] == b'_' || bytes[end] == b'-')
+            {
+                end += 1;
+            }
+            if end > start {
+                let name = &template[start..end];
+                match params.iter().find(|(n, _)| *n == name) {
+                    Some((_, value)) => out.push_str(value),
+                    None => out.push_str(&template[i..end]),
+                }
+                i = end;
+                continue;
+            }
+        }
+        let ch = template[i..].chars().next().unwrap();
+        out.push(ch);
+        i += ch.len_utf8();
+    }
+    out
+}
+
+";
+
+/// Generates a Rust module with one typed accessor function per sentence in
+/// `translations.json` and a `Lang` enum built from its `Languages` map, so a missing or
+/// renamed key becomes a build error instead of a silent empty string. Intended to be called
+/// from a `build.rs` via `--emit-rust <path>`.
+pub(crate) fn emit_rust(langs: &[String], sentences: &[HashMap<String, String>], out_path: &str) -> Result<()> {
+    let placeholder_re = Regex::new(r"\$[a-zA-Z0-9_-]+").unwrap();
+
+    let mut module = String::new();
+    module.push_str("// @generated by nhm_crowdin_parser --emit-rust. Do not edit by hand.\n\n");
+
+    module.push_str("#[derive(Clone, Copy, Debug, PartialEq, Eq)]\npub enum Lang {\n");
+    for lang in langs.iter() {
+        module.push_str(&format!("    {},\n", lang_variant(lang)));
+    }
+    module.push_str("}\n\n");
+
+    module.push_str("impl Lang {\n    fn code(self) -> &'static str {\n        match self {\n");
+    for lang in langs.iter() {
+        module.push_str(&format!("            Lang::{} => {:?},\n", lang_variant(lang), lang));
+    }
+    module.push_str("        }\n    }\n}\n\n");
+
+    module.push_str(INTERPOLATE_HELPER);
+
+    let mut used_names: HashSet<String> = HashSet::new();
+    for sentence in sentences.iter() {
+        let en = sentence.get("en").cloned().unwrap_or_default();
+        let params = placeholders(&en, &placeholder_re);
+
+        let mut name = slug(&en);
+        while !used_names.insert(name.clone()) {
+            name.push('_');
+        }
+
+        let mut seen_idents: HashSet<String> = HashSet::new();
+        let param_idents: Vec<String> = params
+            .iter()
+            .map(|p| {
+                let mut ident = param_ident(p);
+                while !seen_idents.insert(ident.clone()) {
+                    ident.push('_');
+                }
+                ident
+            })
+            .collect();
+
+        let fn_params: String = param_idents.iter().map(|ident| format!(", {}: &str", ident)).collect();
+        module.push_str(&format!("pub fn {}(lang: Lang{}) -> String {{\n", name, fn_params));
+        module.push_str("    let template = match lang.code() {\n");
+        for lang in langs.iter() {
+            let value = sentence.get(lang).cloned().unwrap_or_else(|| en.clone());
+            module.push_str(&format!("        {:?} => {:?},\n", lang, value));
+        }
+        module.push_str(&format!("        _ => {:?},\n", en));
+        module.push_str("    };\n");
+        if params.is_empty() {
+            module.push_str("    template.to_string()\n}\n\n");
+        } else {
+            let args: String = params
+                .iter()
+                .zip(param_idents.iter())
+                .map(|(p, ident)| format!("({:?}, {})", p, ident))
+                .collect::<Vec<_>>()
+                .join(", ");
+            module.push_str(&format!("    interpolate(template, &[{}])\n}}\n\n", args));
+        }
+    }
+
+    let mut file = File::create(out_path)?;
+    file.write_all(module.as_bytes())?;
+    file.sync_all()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mirrors the `interpolate` function emitted into generated code via `INTERPOLATE_HELPER`
+    /// above — kept here so the substitution algorithm has direct unit test coverage without
+    /// having to compile and execute generated output. Must be kept in sync with the string.
+    fn interpolate(template: &str, params: &[(&str, &str)]) -> String {
+        let mut out = String::with_capacity(template.len());
+        let bytes = template.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'$' {
+                let start = i + 1;
+                let mut end = start;
+                while end < bytes.len()
+                    && (bytes[end].is_ascii_alphanumeric() || bytes[end] == b'_' || bytes[end] == b'-')
+                {
+                    end += 1;
+                }
+                if end > start {
+                    let name = &template[start..end];
+                    match params.iter().find(|(n, _)| *n == name) {
+                        Some((_, value)) => out.push_str(value),
+                        None => out.push_str(&template[i..end]),
+                    }
+                    i = end;
+                    continue;
+                }
+            }
+            let ch = template[i..].chars().next().unwrap();
+            out.push(ch);
+            i += ch.len_utf8();
+        }
+        out
+    }
+
+    #[test]
+    fn placeholders_collects_distinct_names_in_first_appearance_order() {
+        let re = Regex::new(r"\$[a-zA-Z0-9_-]+").unwrap();
+        let found = placeholders("$countMax items, $count left, $countMax total", &re);
+        assert_eq!(found, vec!["countMax".to_string(), "count".to_string()]);
+    }
+
+    #[test]
+    fn slug_normalizes_punctuation_and_case() {
+        assert_eq!(slug("Hello, World!"), "hello_world");
+        assert_eq!(slug("$count items left"), "count_items_left");
+    }
+
+    #[test]
+    fn slug_prefixes_names_that_start_with_a_digit() {
+        assert_eq!(slug("3 items left"), "tr_3_items_left");
+    }
+
+    #[test]
+    fn lang_variant_converts_locale_codes_to_pascal_case() {
+        assert_eq!(lang_variant("zh_cn"), "ZhCn");
+        assert_eq!(lang_variant("en"), "En");
+    }
+
+    #[test]
+    fn param_ident_sanitizes_hyphens_and_leading_digits() {
+        assert_eq!(param_ident("user-name"), "user_name");
+        assert_eq!(param_ident("1"), "p_1");
+    }
+
+    #[test]
+    fn interpolate_matches_placeholders_as_whole_tokens() {
+        let out = interpolate(
+            "You have $count items ($countMax max)",
+            &[("count", "3"), ("countMax", "10")],
+        );
+        assert_eq!(out, "You have 3 items (10 max)");
+    }
+
+    #[test]
+    fn interpolate_leaves_unknown_placeholders_untouched() {
+        let out = interpolate("Hi $name", &[]);
+        assert_eq!(out, "Hi $name");
+    }
+}
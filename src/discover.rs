@@ -0,0 +1,138 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+use anyhow::{bail, Result};
+use clap::ValueEnum;
+
+use crate::TranslationFile;
+
+/// How to resolve an English-sentence key that appears in more than one glob-matched file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub(crate) enum OnConflict {
+    /// Keep the translations from whichever file was merged first.
+    First,
+    /// Overwrite with the translations from whichever file was merged last.
+    Last,
+    /// Abort the run when the same English sentence appears in more than one file.
+    Error,
+}
+
+/// Expands `pattern` (e.g. `modules/**/locales/*.json`) to a sorted list of matching paths,
+/// so repeated runs merge files in the same deterministic order regardless of filesystem
+/// iteration order.
+fn discover_paths(pattern: &str) -> Result<Vec<PathBuf>> {
+    let mut paths: Vec<PathBuf> = glob::glob(pattern)?.filter_map(|p| p.ok()).collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Reads every `TranslationFile` matched by `pattern` and merges their `Languages` and
+/// `Translations` maps into one, using `on_conflict` to resolve English-sentence key
+/// collisions across files.
+pub(crate) fn discover_and_merge(pattern: &str, on_conflict: OnConflict) -> Result<TranslationFile> {
+    let paths = discover_paths(pattern)?;
+    if paths.is_empty() {
+        bail!("no files matched glob pattern {:?}", pattern);
+    }
+
+    let mut languages: HashMap<String, String> = HashMap::new();
+    let mut translations: HashMap<String, HashMap<String, String>> = HashMap::new();
+
+    for path in paths.iter() {
+        let f = File::open(path)?;
+        let reader = BufReader::new(f);
+        let file: TranslationFile = serde_json::from_reader(reader)?;
+
+        languages.extend(file.languages);
+
+        for (en_sentence, word_translations) in file.translations {
+            match translations.entry(en_sentence) {
+                Entry::Vacant(e) => {
+                    e.insert(word_translations);
+                }
+                Entry::Occupied(mut e) => match on_conflict {
+                    OnConflict::First => {}
+                    OnConflict::Last => {
+                        e.insert(word_translations);
+                    }
+                    OnConflict::Error => {
+                        bail!("key {:?} in {:?} collides with an earlier file", e.key(), path);
+                    }
+                },
+            }
+        }
+    }
+
+    Ok(TranslationFile { languages, translations })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// Writes two fixture `TranslationFile`s that share the `"Hello"` key with different
+    /// translations, so the three `OnConflict` policies can be told apart, into a fresh temp
+    /// directory unique to this test run.
+    fn write_fixtures(tag: &str) -> std::path::PathBuf {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+        let dir = std::env::temp_dir().join(format!("nhm_discover_test_{}_{}", tag, nanos));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        std::fs::write(
+            dir.join("a_first.json"),
+            r#"{"Languages":{"en":"English","es":"Espanol"},"Translations":{"Hello":{"es":"Hola A"}}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("b_second.json"),
+            r#"{"Languages":{"en":"English","fr":"Francais"},"Translations":{"Hello":{"es":"Hola B"},"Bye":{"fr":"Au revoir"}}}"#,
+        )
+        .unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn first_keeps_the_earliest_files_translation() {
+        let dir = write_fixtures("first");
+        let merged = discover_and_merge(&dir.join("*.json").to_string_lossy(), OnConflict::First).unwrap();
+        assert_eq!(merged.translations["Hello"]["es"], "Hola A");
+        assert_eq!(merged.translations["Bye"]["fr"], "Au revoir");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn last_keeps_the_latest_files_translation() {
+        let dir = write_fixtures("last");
+        let merged = discover_and_merge(&dir.join("*.json").to_string_lossy(), OnConflict::Last).unwrap();
+        assert_eq!(merged.translations["Hello"]["es"], "Hola B");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn error_aborts_on_a_colliding_key() {
+        let dir = write_fixtures("error");
+        let result = discover_and_merge(&dir.join("*.json").to_string_lossy(), OnConflict::Error);
+        assert!(result.is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn merges_language_maps_across_files() {
+        let dir = write_fixtures("langs");
+        let merged = discover_and_merge(&dir.join("*.json").to_string_lossy(), OnConflict::First).unwrap();
+        assert_eq!(merged.languages.len(), 3);
+        assert!(merged.languages.contains_key("fr"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn errors_when_the_glob_matches_nothing() {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+        let pattern = std::env::temp_dir().join(format!("nhm_discover_empty_{}/*.json", nanos));
+        assert!(discover_and_merge(&pattern.to_string_lossy(), OnConflict::First).is_err());
+    }
+}
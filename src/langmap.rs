@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+use anyhow::Result;
+use serde::Deserialize;
+
+/// One `--lang-map` entry: either a plain display name, or a display name plus an explicit
+/// "unofficial" flag controlling whether `--unofficial-suffix` gets appended to it.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LangEntry {
+    Name(String),
+    Detailed {
+        display_name: String,
+        #[serde(default)]
+        unofficial: bool,
+    },
+}
+
+impl LangEntry {
+    fn resolve(&self, unofficial_suffix: &str) -> String {
+        match self {
+            LangEntry::Name(name) => name.clone(),
+            LangEntry::Detailed { display_name, unofficial } if *unofficial => {
+                format!("{} {}", display_name, unofficial_suffix)
+            }
+            LangEntry::Detailed { display_name, .. } => display_name.clone(),
+        }
+    }
+}
+
+/// A loadable `code -> display name` mapping, replacing the old hardcoded `to_language` match.
+pub(crate) struct LangMap {
+    entries: BTreeMap<String, LangEntry>,
+}
+
+impl LangMap {
+    /// Loads entries from `--lang-map <file.json>` when given, otherwise falls back to the
+    /// bundled BCP-47 locale table this crate ships with.
+    pub(crate) fn load(lang_map_path: Option<&str>) -> Result<LangMap> {
+        match lang_map_path {
+            Some(path) => {
+                let f = File::open(path)?;
+                let reader = BufReader::new(f);
+                let entries: BTreeMap<String, LangEntry> = serde_json::from_reader(reader)?;
+                Ok(LangMap { entries })
+            }
+            None => Ok(LangMap { entries: bundled_table() }),
+        }
+    }
+
+    /// Resolves `lang` to a display name, appending `unofficial_suffix` when the entry is
+    /// flagged unofficial. Warns on stderr and returns the legacy `LANG_STUB` sentinel (instead
+    /// of failing silently) when `lang` has no entry in the map.
+    pub(crate) fn to_language(&self, lang: &str, unofficial_suffix: &str) -> String {
+        match self.entries.get(lang) {
+            Some(entry) => entry.resolve(unofficial_suffix),
+            None => {
+                eprintln!(
+                    "warning: no display name mapped for language code {:?}; emitting LANG_STUB",
+                    lang
+                );
+                "LANG_STUB".to_string()
+            }
+        }
+    }
+}
+
+fn bundled_table() -> BTreeMap<String, LangEntry> {
+    let raw: &[(&str, &str, bool)] = &[
+        ("en", "English", false),
+        ("ru", "Русский", true),
+        ("es", "Español", true),
+        ("pt", "Português", true),
+        ("bg", "Български", true),
+        ("it", "Italiano", true),
+        ("pl", "Polski", true),
+        ("zh_cn", "简体中文", true),
+        ("ro", "Română", true),
+    ];
+    raw.iter()
+        .map(|(code, name, unofficial)| {
+            (
+                code.to_string(),
+                LangEntry::Detailed {
+                    display_name: name.to_string(),
+                    unofficial: *unofficial,
+                },
+            )
+        })
+        .collect()
+}
@@ -6,15 +6,36 @@ use std::io::prelude::*;
 use std::io::BufReader;
 use std::fs::{File, self, DirEntry};
 use std::path::{Path, PathBuf};
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+
+mod po;
+mod mo;
+mod validate;
+mod discover;
+mod codegen;
+mod sqlite;
+mod langmap;
 
 static TRANSLATIONS_JSON: &str = "translations.json";
 static CROWDIN_DIR: &str = "crowdin";
 
+/// Serialization format used for the transformed output (or input, when `--reverse`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Crowdin's per-language JSON key/value files (the original format).
+    Crowdin,
+    /// GNU gettext `.po` catalogs, one per language.
+    Po,
+    /// Compiled GNU gettext `.mo` catalogs, one per language (read-only).
+    Mo,
+}
+
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
-    /// Input path for transforming
+    /// Input path for transforming, or (for the forward `--format crowdin` conversion and for
+    /// --emit-rust) a glob pattern such as `modules/**/locales/*.json` matching several NHM
+    /// translation files to merge
     #[clap(short, long, value_parser, default_value = TRANSLATIONS_JSON)]
     input: String,
 
@@ -25,18 +46,50 @@ struct Args {
     /// Inverse
     #[clap(short, long)]
     reverse: bool,
+
+    /// Format of the crowdin-side input/output
+    #[clap(short, long, value_enum, default_value_t = Format::Crowdin)]
+    format: Format,
+
+    /// Validate that $placeholders in every translation match the English source before
+    /// writing the merged translations.json
+    #[clap(long)]
+    validate: bool,
+
+    /// Report --validate mismatches as warnings instead of failing the run
+    #[clap(long)]
+    validate_warn_only: bool,
+
+    /// How to resolve English-sentence key collisions when --input glob-matches multiple files
+    #[clap(long, value_enum, default_value_t = discover::OnConflict::First)]
+    on_conflict: discover::OnConflict,
+
+    /// Generate a Rust module of typed translation accessors from --input and exit, for use
+    /// from a build.rs
+    #[clap(long, value_name = "PATH")]
+    emit_rust: Option<String>,
+
+    /// Optional `{ "code": "Display Name" }` mapping of language display names, replacing the
+    /// bundled locale table
+    #[clap(long, value_name = "FILE")]
+    lang_map: Option<String>,
+
+    /// Suffix appended to display names flagged as unofficial (the bundled table, or a
+    /// --lang-map entry with `"unofficial": true`)
+    #[clap(long, default_value = "(Unofficial)")]
+    unofficial_suffix: String,
 }
 
 #[derive(Deserialize)]
-struct TranslationFile {
+pub(crate) struct TranslationFile {
     #[serde(rename = "Languages")]
-    languages: HashMap<String, String>,
+    pub(crate) languages: HashMap<String, String>,
     #[serde(rename = "Translations")]
-    translations: HashMap<String, HashMap<String, String>>,
+    pub(crate) translations: HashMap<String, HashMap<String, String>>,
 }
 
 impl TranslationFile {
-    fn consume(self) -> (Vec<String>, Vec<HashMap<String, String>>) {
+    pub(crate) fn consume(self) -> (Vec<String>, Vec<HashMap<String, String>>) {
         let langs: Vec<String> = self.languages.into_keys().collect();
         let sentences: Vec<HashMap<String, String>> = self.translations
         .into_iter()
@@ -54,7 +107,7 @@ impl TranslationFile {
     }
 }
 
-fn nhm_translations_to_crowdin(translations_path: &str, out_dir: &str) -> Result<()> {
+fn nhm_translations_to_crowdin(input_glob: &str, out_dir: &str, on_conflict: discover::OnConflict) -> Result<()> {
     fn key_str(i: usize, max: usize) -> String {
         let d1 = i.to_string().len();
         let d2 = max.to_string().len();
@@ -62,13 +115,7 @@ fn nhm_translations_to_crowdin(translations_path: &str, out_dir: &str) -> Result
         format!("k_{}{}", zeros, i)
     }
 
-    let read_translations_json = || -> Result<(Vec<String>, Vec<HashMap<String, String>>)> {
-        let f = File::open(translations_path)?;
-        let reader = BufReader::new(f);
-        let tr_file: TranslationFile = serde_json::from_reader(reader)?;
-        Ok(tr_file.consume())
-    };
-    let (langs, sentences) = read_translations_json()?;
+    let (langs, sentences) = discover::discover_and_merge(input_glob, on_conflict)?.consume();
 
     let max = sentences.capacity();
     let create_lang_file = |lang: &str| -> Result<()> {
@@ -94,7 +141,62 @@ fn nhm_translations_to_crowdin(translations_path: &str, out_dir: &str) -> Result
     Ok(())
 }
 
-fn crowdin_to_nhm_translations(crowdin_dir: &str, out_translations_path: &str) -> Result<()> {
+/// Builds an NHM `translations.json` from a per-language map of `msgid -> msgstr`
+/// (English included under the key `"en"`) and writes it to `out_translations_path`.
+pub(crate) fn write_nhm_translations(
+    mut langs: BTreeMap<String, BTreeMap<String, String>>,
+    out_translations_path: &str,
+    lang_map: &langmap::LangMap,
+    unofficial_suffix: &str,
+) -> Result<()> {
+    let languages: HashMap<String, String> = langs
+        .keys()
+        .map(|l| (l.to_owned(), lang_map.to_language(l, unofficial_suffix)))
+        .collect();
+    let mut translations: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+    let en = langs.remove_entry("en").unwrap().1;
+    for key in en.keys() {
+        let en_word = en.get(key).unwrap();
+        translations.insert(en_word.to_owned(), BTreeMap::new());
+        let word_translations = translations.get_mut(en_word).unwrap();
+
+        for lang in langs.keys() {
+            if let Some(tr_word) = langs.get(lang).unwrap().get(key) {
+                if tr_word.ne("") {
+                    word_translations.insert(lang.to_owned(), tr_word.to_owned());
+                }
+            }
+        }
+    }
+
+    #[derive(Serialize)]
+    struct SaveFile {
+        #[serde(rename = "Languages")]
+        languages: HashMap<String, String>,
+        #[serde(rename = "Translations")]
+        translations: BTreeMap<String, BTreeMap<String, String>>,
+    }
+
+    let save_file = SaveFile {
+        languages: languages,
+        translations: translations,
+    };
+
+    let mut file = File::create(out_translations_path)?;
+    file.write_all(serde_json::to_string_pretty(&save_file)?.as_bytes())?;
+    file.sync_all()?;
+
+    Ok(())
+}
+
+fn crowdin_to_nhm_translations(
+    crowdin_dir: &str,
+    out_translations_path: &str,
+    validate: bool,
+    validate_warn_only: bool,
+    lang_map: &langmap::LangMap,
+    unofficial_suffix: &str,
+) -> Result<()> {
     fn maybe_lang_key(d: &DirEntry) -> Option<(String, PathBuf)> {
         fn is_file(d: &DirEntry) -> bool { d.file_type().map_or(false,|d| d.is_file()) }
         fn get_file_name(d: &DirEntry) -> String { d.file_name().into_string().ok().unwrap_or("".to_string()) }
@@ -109,22 +211,6 @@ fn crowdin_to_nhm_translations(crowdin_dir: &str, out_translations_path: &str) -
         None
     }
 
-    fn to_language(lang: &str) -> String {
-        let ret = match lang {
-            "en" => "English",
-            "ru" => "Русский (Unofficial)",
-            "es" => "Español (Unofficial)",
-            "pt" => "Português (Unofficial)",
-            "bg" => "Български (Unofficial)",
-            "it" => "Italiano (Unofficial)",
-            "pl" => "Polski (Unofficial)",
-            "zh_cn" => "简体中文 (Unofficial)",
-            "ro" => "Română (Unofficial)",
-            _ => "LANG_STUB",
-        };
-        ret.to_string()
-    }
-
     fn read_crowdin_translations_json(translations_path: &str) -> Result<BTreeMap<String, String>> {
         let f = File::open(translations_path)?;
         let reader = BufReader::new(f);
@@ -142,41 +228,10 @@ fn crowdin_to_nhm_translations(crowdin_dir: &str, out_translations_path: &str) -
         let words = read_crowdin_translations_json(path.to_str().unwrap())?;
         langs.insert(lang, words);
     }
-    let languages: HashMap<String, String> = langs.keys().map(|l| (l.to_owned(), to_language(l))).collect();
-    let mut translations: BTreeMap<&String, BTreeMap<&String, &String>> = BTreeMap::new();
-    let en = langs.remove_entry("en").unwrap().1;
-    for key in en.keys() {
-        let en_word = en.get(key).unwrap();
-        translations.insert(en_word, BTreeMap::new());
-        let word_translations = translations.get_mut(en_word).unwrap();
 
-        for lang in langs.keys() {
-            if let Some(tr_word) = langs.get(lang).unwrap().get(key) {
-                if tr_word.ne("") {
-                    word_translations.insert(lang, tr_word);
-                }
-            }
-        }
-    }
+    validate::check_and_report(&langs, validate, validate_warn_only)?;
 
-    #[derive(Serialize)]
-    struct SaveFile<'a> {
-        #[serde(rename = "Languages")]
-        languages: HashMap<String, String>,
-        #[serde(rename = "Translations")]
-        translations: BTreeMap<&'a String, BTreeMap<&'a String, &'a String>>,
-    }
-
-    let save_file = SaveFile {
-        languages: languages,
-        translations: translations,
-    };
-
-    let mut file = File::create(out_translations_path)?;
-    file.write_all(serde_json::to_string_pretty(&save_file)?.as_bytes())?;
-    file.sync_all()?;
-
-    Ok(())
+    write_nhm_translations(langs, out_translations_path, lang_map, unofficial_suffix)
 }
 
 fn transform_default_args(reverse: bool, input: &str, output: &str) -> Result<(String, String)> {
@@ -192,13 +247,61 @@ fn transform_default_args(reverse: bool, input: &str, output: &str) -> Result<(S
 
 fn main() -> Result<()> {
     let args = Args::parse();
+
+    if let Some(emit_rust_path) = args.emit_rust.as_deref() {
+        let (langs, sentences) = discover::discover_and_merge(&args.input, args.on_conflict)?.consume();
+        return codegen::emit_rust(&langs, &sentences, emit_rust_path);
+    }
+
     let (input, output) = transform_default_args(args.reverse, &args.input, &args.output)?;
     println!("{} {} {}", args.reverse, input, output);
-    if args.reverse {
-        crowdin_to_nhm_translations(&input, &output)?;
+
+    let lang_map = langmap::LangMap::load(args.lang_map.as_deref())?;
+
+    if !args.reverse && sqlite::is_sqlite_path(&output) {
+        sqlite::nhm_translations_to_sqlite(&input, &output, &lang_map, &args.unofficial_suffix)?;
+        return Ok(());
     }
-    else {
-        nhm_translations_to_crowdin(&input, &output)?;
+    if args.reverse && sqlite::is_sqlite_path(&input) {
+        sqlite::sqlite_to_nhm_translations(
+            &input,
+            &output,
+            args.validate,
+            args.validate_warn_only,
+            &lang_map,
+            &args.unofficial_suffix,
+        )?;
+        return Ok(());
+    }
+
+    match (args.reverse, args.format) {
+        (false, Format::Crowdin) => nhm_translations_to_crowdin(&input, &output, args.on_conflict)?,
+        (false, Format::Po) => po::nhm_translations_to_po(&input, &output)?,
+        (false, Format::Mo) => anyhow::bail!("writing .mo catalogs is not supported, use --format po instead"),
+        (true, Format::Crowdin) => crowdin_to_nhm_translations(
+            &input,
+            &output,
+            args.validate,
+            args.validate_warn_only,
+            &lang_map,
+            &args.unofficial_suffix,
+        )?,
+        (true, Format::Po) => po::po_to_nhm_translations(
+            &input,
+            &output,
+            args.validate,
+            args.validate_warn_only,
+            &lang_map,
+            &args.unofficial_suffix,
+        )?,
+        (true, Format::Mo) => mo::mo_to_nhm_translations(
+            &input,
+            &output,
+            args.validate,
+            args.validate_warn_only,
+            &lang_map,
+            &args.unofficial_suffix,
+        )?,
     }
     Ok(())
 }
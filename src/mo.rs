@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+use std::fs;
+use anyhow::{bail, Result};
+
+use crate::write_nhm_translations;
+
+const MAGIC_LE: u32 = 0x950412de;
+const MAGIC_BE: u32 = 0xde120495;
+
+fn read_u32(data: &[u8], offset: usize, swap: bool) -> Result<u32> {
+    let bytes: [u8; 4] = data
+        .get(offset..offset + 4)
+        .ok_or_else(|| anyhow::anyhow!("truncated .mo file: offset {} out of range", offset))?
+        .try_into()?;
+    Ok(if swap {
+        u32::from_be_bytes(bytes)
+    } else {
+        u32::from_le_bytes(bytes)
+    })
+}
+
+/// Parses a compiled gettext `.mo` catalog into its `(msgid, msgstr)` pairs, in file order.
+///
+/// Implements the format directly (no `msgfmt`/`libgettextpo` dependency): a 32-bit magic
+/// number at offset 0 selects the byte order, the string count sits at offset 8, and offsets
+/// 12/16 point at the original/translated string tables. Each table holds `count` entries of
+/// two little/big-endian `u32`s (`length`, `offset`) describing a UTF-8 slice in the file.
+pub(crate) fn read_mo_file(data: &[u8]) -> Result<Vec<(String, String)>> {
+    let magic = read_u32(data, 0, false)?;
+    let swap = match magic {
+        MAGIC_LE => false,
+        MAGIC_BE => true,
+        _ => bail!("not a .mo file: unrecognized magic {:#x}", magic),
+    };
+
+    let count = read_u32(data, 8, swap)? as usize;
+    let orig_table_offset = read_u32(data, 12, swap)? as usize;
+    let trans_table_offset = read_u32(data, 16, swap)? as usize;
+
+    let table_bytes = count
+        .checked_mul(8)
+        .ok_or_else(|| anyhow::anyhow!("string count overflow in .mo file"))?;
+    let required = orig_table_offset.max(trans_table_offset) + table_bytes;
+    if data.len() < required {
+        bail!(
+            "truncated .mo file: need at least {} bytes, got {}",
+            required,
+            data.len()
+        );
+    }
+
+    let mut pairs = Vec::with_capacity(count);
+    for i in 0..count {
+        let orig_entry = orig_table_offset + i * 8;
+        let trans_entry = trans_table_offset + i * 8;
+
+        let orig_len = read_u32(data, orig_entry, swap)? as usize;
+        let orig_pos = read_u32(data, orig_entry + 4, swap)? as usize;
+        let trans_len = read_u32(data, trans_entry, swap)? as usize;
+        let trans_pos = read_u32(data, trans_entry + 4, swap)? as usize;
+
+        let msgid = data
+            .get(orig_pos..orig_pos + orig_len)
+            .ok_or_else(|| anyhow::anyhow!("truncated .mo file: original string {} out of range", i))?;
+        let msgstr = data
+            .get(trans_pos..trans_pos + trans_len)
+            .ok_or_else(|| anyhow::anyhow!("truncated .mo file: translated string {} out of range", i))?;
+
+        pairs.push((
+            String::from_utf8_lossy(msgid).into_owned(),
+            String::from_utf8_lossy(msgstr).into_owned(),
+        ));
+    }
+
+    Ok(pairs)
+}
+
+/// Reads every `tr_<lang>.mo` file in `mo_dir` and assembles them back into an
+/// NHM `translations.json`, mirroring `crowdin_to_nhm_translations`.
+pub(crate) fn mo_to_nhm_translations(
+    mo_dir: &str,
+    out_translations_path: &str,
+    validate: bool,
+    validate_warn_only: bool,
+    lang_map: &crate::langmap::LangMap,
+    unofficial_suffix: &str,
+) -> Result<()> {
+    let mut langs: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+
+    for entry in fs::read_dir(mo_dir)? {
+        let entry = entry?;
+        if !entry.file_type().map_or(false, |t| t.is_file()) {
+            continue;
+        }
+        let file_name = entry.file_name().into_string().unwrap_or_default();
+        if !(file_name.starts_with("tr_") && file_name.ends_with(".mo")) {
+            continue;
+        }
+        let lang = file_name.replace("tr_", "").replace(".mo", "");
+        let data = fs::read(entry.path())?;
+        let words: BTreeMap<String, String> = read_mo_file(&data)?
+            .into_iter()
+            .filter(|(msgid, _)| !msgid.is_empty())
+            .collect();
+        langs.insert(lang, words);
+    }
+
+    crate::validate::check_and_report(&langs, validate, validate_warn_only)?;
+
+    write_nhm_translations(langs, out_translations_path, lang_map, unofficial_suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hand-assembles a minimal `.mo` file for `pairs` in the requested byte order, so the
+    /// table-parsing logic in `read_mo_file` can be exercised without a real `msgfmt` binary.
+    fn build_mo(pairs: &[(&str, &str)], big_endian: bool) -> Vec<u8> {
+        let put = |v: u32| if big_endian { v.to_be_bytes() } else { v.to_le_bytes() };
+        let count = pairs.len() as u32;
+        let orig_table_offset: u32 = 28;
+        let trans_table_offset = orig_table_offset + count * 8;
+        let strings_offset = trans_table_offset + count * 8;
+
+        let mut orig_blob = Vec::new();
+        let mut orig_entries = Vec::new();
+        for (msgid, _) in pairs {
+            orig_entries.push((msgid.len() as u32, strings_offset + orig_blob.len() as u32));
+            orig_blob.extend_from_slice(msgid.as_bytes());
+            orig_blob.push(0);
+        }
+        let trans_blob_offset = strings_offset + orig_blob.len() as u32;
+        let mut trans_blob = Vec::new();
+        let mut trans_entries = Vec::new();
+        for (_, msgstr) in pairs {
+            trans_entries.push((msgstr.len() as u32, trans_blob_offset + trans_blob.len() as u32));
+            trans_blob.extend_from_slice(msgstr.as_bytes());
+            trans_blob.push(0);
+        }
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&put(0x950412de)); // magic, always in "native" value; put() picks the byte order
+        data.extend_from_slice(&put(0)); // revision
+        data.extend_from_slice(&put(count));
+        data.extend_from_slice(&put(orig_table_offset));
+        data.extend_from_slice(&put(trans_table_offset));
+        data.extend_from_slice(&put(0)); // hash table size
+        data.extend_from_slice(&put(0)); // hash table offset
+        for (len, off) in &orig_entries {
+            data.extend_from_slice(&put(*len));
+            data.extend_from_slice(&put(*off));
+        }
+        for (len, off) in &trans_entries {
+            data.extend_from_slice(&put(*len));
+            data.extend_from_slice(&put(*off));
+        }
+        data.extend_from_slice(&orig_blob);
+        data.extend_from_slice(&trans_blob);
+        data
+    }
+
+    #[test]
+    fn reads_little_endian_mo_file() {
+        let pairs = [("", "Content-Type: text/plain; charset=UTF-8\n"), ("Hello", "Hola")];
+        let data = build_mo(&pairs, false);
+        let parsed = read_mo_file(&data).unwrap();
+        assert_eq!(parsed, pairs.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn reads_big_endian_mo_file() {
+        let pairs = [("Hello", "Hola"), ("Bye", "Adios")];
+        let data = build_mo(&pairs, true);
+        let parsed = read_mo_file(&data).unwrap();
+        assert_eq!(parsed, pairs.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn rejects_unrecognized_magic() {
+        let data = vec![0u8; 32];
+        assert!(read_mo_file(&data).is_err());
+    }
+
+    #[test]
+    fn rejects_truncated_file() {
+        let data = build_mo(&[("Hello", "Hola")], false);
+        let truncated = &data[..data.len() - 4];
+        assert!(read_mo_file(truncated).is_err());
+    }
+}
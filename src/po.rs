@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::prelude::*;
+use std::io::BufReader;
+use std::path::Path;
+use anyhow::Result;
+
+use crate::{write_nhm_translations, TranslationFile};
+
+fn escape_po_string(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn unescape_po_string(s: &str) -> String {
+    let mut out = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('"') => out.push('"'),
+            Some('\\') => out.push('\\'),
+            Some(other) => { out.push('\\'); out.push(other); }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Writes one gettext `.po` catalog per language, named `tr_<lang>.po`, with one
+/// `msgid`/`msgstr` pair per sentence. The English sentence is always the `msgid`;
+/// `msgstr` is empty when the language has no translation for it.
+pub(crate) fn nhm_translations_to_po(translations_path: &str, out_dir: &str) -> Result<()> {
+    let f = File::open(translations_path)?;
+    let reader = BufReader::new(f);
+    let tr_file: TranslationFile = serde_json::from_reader(reader)?;
+    let (langs, sentences) = tr_file.consume();
+
+    for lang in langs.iter() {
+        let mut out = String::new();
+        out.push_str("msgid \"\"\nmsgstr \"\"\n\"Content-Type: text/plain; charset=UTF-8\\n\"\n\n");
+        for sentence in sentences.iter() {
+            let en = sentence.get("en").map(String::as_str).unwrap_or("");
+            let msgstr = sentence.get(lang).map(String::as_str).unwrap_or("");
+            out.push_str(&format!("msgid \"{}\"\n", escape_po_string(en)));
+            out.push_str(&format!("msgstr \"{}\"\n\n", escape_po_string(msgstr)));
+        }
+
+        let out_path = format!("{}/tr_{}.po", out_dir, lang);
+        let out_path = Path::new(&out_path);
+        fs::create_dir_all(out_path.parent().unwrap())?;
+
+        let mut file = File::create(out_path)?;
+        file.write_all(out.as_bytes())?;
+        file.sync_all()?;
+    }
+    Ok(())
+}
+
+/// Parses a single `.po` file into an ordered `msgid -> msgstr` map.
+fn read_po_file(path: &Path) -> Result<BTreeMap<String, String>> {
+    let contents = fs::read_to_string(path)?;
+    Ok(parse_po(&contents))
+}
+
+/// Parses the textual contents of a `.po` file into an ordered `msgid -> msgstr` map, skipping
+/// comments and the empty-`msgid` header entry.
+fn parse_po(contents: &str) -> BTreeMap<String, String> {
+    let mut result = BTreeMap::new();
+
+    let mut msgid: Option<String> = None;
+    let mut msgstr: Option<String> = None;
+
+    fn quoted(line: &str) -> Option<&str> {
+        let line = line.trim();
+        let start = line.find('"')?;
+        let end = line.rfind('"')?;
+        if end <= start {
+            return None;
+        }
+        Some(&line[start + 1..end])
+    }
+
+    fn flush(msgid: &mut Option<String>, msgstr: &mut Option<String>, result: &mut BTreeMap<String, String>) {
+        if let (Some(id), Some(s)) = (msgid.take(), msgstr.take()) {
+            if !id.is_empty() {
+                result.insert(id, s);
+            }
+        }
+    }
+
+    for line in contents.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some(rest) = trimmed.strip_prefix("msgid") {
+            flush(&mut msgid, &mut msgstr, &mut result);
+            msgid = Some(unescape_po_string(quoted(rest).unwrap_or("")));
+        } else if let Some(rest) = trimmed.strip_prefix("msgstr") {
+            msgstr = Some(unescape_po_string(quoted(rest).unwrap_or("")));
+        } else if trimmed.starts_with('"') {
+            let extra = unescape_po_string(quoted(trimmed).unwrap_or(""));
+            if let Some(s) = msgstr.as_mut() {
+                s.push_str(&extra);
+            } else if let Some(id) = msgid.as_mut() {
+                id.push_str(&extra);
+            }
+        }
+    }
+    flush(&mut msgid, &mut msgstr, &mut result);
+
+    result
+}
+
+/// Reads every `tr_<lang>.po` file in `po_dir` and assembles them back into an
+/// NHM `translations.json`, mirroring `crowdin_to_nhm_translations`.
+pub(crate) fn po_to_nhm_translations(
+    po_dir: &str,
+    out_translations_path: &str,
+    validate: bool,
+    validate_warn_only: bool,
+    lang_map: &crate::langmap::LangMap,
+    unofficial_suffix: &str,
+) -> Result<()> {
+    let mut langs: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+
+    for entry in fs::read_dir(po_dir)? {
+        let entry = entry?;
+        if !entry.file_type().map_or(false, |t| t.is_file()) {
+            continue;
+        }
+        let file_name = entry.file_name().into_string().unwrap_or_default();
+        if !(file_name.starts_with("tr_") && file_name.ends_with(".po")) {
+            continue;
+        }
+        let lang = file_name.replace("tr_", "").replace(".po", "");
+        let words = read_po_file(&entry.path())?;
+        langs.insert(lang, words);
+    }
+
+    crate::validate::check_and_report(&langs, validate, validate_warn_only)?;
+
+    write_nhm_translations(langs, out_translations_path, lang_map, unofficial_suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_backslashes_quotes_and_newlines() {
+        let raw = "She said \"hi\"\\no\n";
+        let escaped = escape_po_string(raw);
+        assert_eq!(escaped, "She said \\\"hi\\\"\\\\no\\n");
+        assert_eq!(unescape_po_string(&escaped), raw);
+    }
+
+    #[test]
+    fn unescape_handles_tabs_and_dangling_backslash() {
+        assert_eq!(unescape_po_string("a\\tb"), "a\tb");
+        assert_eq!(unescape_po_string("trailing\\"), "trailing\\");
+    }
+
+    #[test]
+    fn parse_po_skips_header_and_joins_continuation_lines() {
+        let contents = concat!(
+            "# a comment\n",
+            "msgid \"\"\n",
+            "msgstr \"\"\n",
+            "\"Content-Type: text/plain; charset=UTF-8\\n\"\n",
+            "\n",
+            "msgid \"Hello\"\n",
+            "msgstr \"Hola\"\n",
+            "\n",
+            "msgid \"Line one\\n\"\n",
+            "msgstr \"\"\n",
+            "\"Linea \"\n",
+            "\"uno\\n\"\n",
+        );
+
+        let parsed = parse_po(contents);
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed.get("Hello").map(String::as_str), Some("Hola"));
+        assert_eq!(parsed.get("Line one\n").map(String::as_str), Some("Linea uno\n"));
+    }
+}
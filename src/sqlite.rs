@@ -0,0 +1,208 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+use anyhow::Result;
+use rusqlite::{params, Connection};
+
+use crate::langmap::LangMap;
+use crate::{write_nhm_translations, TranslationFile};
+
+fn create_schema(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS languages (
+            code TEXT PRIMARY KEY,
+            display_name TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS strings (
+            id INTEGER PRIMARY KEY,
+            en_text TEXT NOT NULL UNIQUE
+        );
+        CREATE TABLE IF NOT EXISTS translations (
+            string_id INTEGER NOT NULL REFERENCES strings(id),
+            lang_code TEXT NOT NULL REFERENCES languages(code),
+            text TEXT NOT NULL,
+            PRIMARY KEY (string_id, lang_code)
+        );",
+    )?;
+    Ok(())
+}
+
+/// Returns true for paths ending in `.db` or `.sqlite` (case-insensitive), the extensions that
+/// select the SQLite backend for `--output`/`--input` instead of the usual file formats.
+pub(crate) fn is_sqlite_path(path: &str) -> bool {
+    let lower = path.to_ascii_lowercase();
+    lower.ends_with(".db") || lower.ends_with(".sqlite")
+}
+
+/// Loads `translations_path` and upserts it into a SQLite database at `db_path`, creating the
+/// schema on first use. Upserting (rather than recreating the database) updates the rows of
+/// existing keys in place and inserts new ones; rows for keys no longer present in
+/// `translations_path` are left in the database.
+pub(crate) fn nhm_translations_to_sqlite(
+    translations_path: &str,
+    db_path: &str,
+    lang_map: &LangMap,
+    unofficial_suffix: &str,
+) -> Result<()> {
+    let f = File::open(translations_path)?;
+    let reader = BufReader::new(f);
+    let tr_file: TranslationFile = serde_json::from_reader(reader)?;
+    let (langs, sentences) = tr_file.consume();
+
+    let conn = Connection::open(db_path)?;
+    create_schema(&conn)?;
+
+    for lang in langs.iter() {
+        conn.execute(
+            "INSERT INTO languages (code, display_name) VALUES (?1, ?2)
+             ON CONFLICT(code) DO UPDATE SET display_name = excluded.display_name",
+            params![lang, lang_map.to_language(lang, unofficial_suffix)],
+        )?;
+    }
+
+    for sentence in sentences.iter() {
+        let en = sentence.get("en").cloned().unwrap_or_default();
+        conn.execute(
+            "INSERT INTO strings (en_text) VALUES (?1) ON CONFLICT(en_text) DO NOTHING",
+            params![en],
+        )?;
+        let string_id: i64 = conn.query_row(
+            "SELECT id FROM strings WHERE en_text = ?1",
+            params![en],
+            |row| row.get(0),
+        )?;
+
+        for (lang, text) in sentence.iter() {
+            conn.execute(
+                "INSERT INTO translations (string_id, lang_code, text) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(string_id, lang_code) DO UPDATE SET text = excluded.text",
+                params![string_id, lang, text],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Reconstructs an NHM `translations.json` by joining the `languages`, `strings` and
+/// `translations` tables in `db_path`, mirroring `crowdin_to_nhm_translations`.
+pub(crate) fn sqlite_to_nhm_translations(
+    db_path: &str,
+    out_translations_path: &str,
+    validate: bool,
+    validate_warn_only: bool,
+    lang_map: &LangMap,
+    unofficial_suffix: &str,
+) -> Result<()> {
+    let conn = Connection::open(db_path)?;
+
+    let mut langs: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+
+    let mut lang_stmt = conn.prepare("SELECT code FROM languages")?;
+    let lang_codes = lang_stmt.query_map([], |row| row.get::<_, String>(0))?;
+    for lang in lang_codes {
+        langs.insert(lang?, BTreeMap::new());
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT s.en_text, t.lang_code, t.text
+         FROM translations t
+         JOIN strings s ON s.id = t.string_id",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+    })?;
+    for row in rows {
+        let (en_text, lang_code, text) = row?;
+        langs.entry(lang_code).or_default().insert(en_text, text);
+    }
+
+    crate::validate::check_and_report(&langs, validate, validate_warn_only)?;
+
+    write_nhm_translations(langs, out_translations_path, lang_map, unofficial_suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// Returns a fresh path under the OS temp dir, unique per call so concurrent test runs
+    /// never collide on the same file.
+    fn temp_path(tag: &str) -> std::path::PathBuf {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+        std::env::temp_dir().join(format!("nhm_sqlite_test_{}_{}", tag, nanos))
+    }
+
+    #[test]
+    fn round_trips_translations_through_sqlite() {
+        let translations_path = temp_path("in.json");
+        let db_path = temp_path("out.db");
+        let out_path = temp_path("roundtrip.json");
+
+        std::fs::write(
+            &translations_path,
+            r#"{"Languages":{"en":"English","es":"Espanol"},"Translations":{"Hello":{"es":"Hola"},"Bye":{}}}"#,
+        )
+        .unwrap();
+
+        let lang_map = LangMap::load(None).unwrap();
+        nhm_translations_to_sqlite(
+            translations_path.to_str().unwrap(),
+            db_path.to_str().unwrap(),
+            &lang_map,
+            "(Unofficial)",
+        )
+        .unwrap();
+        sqlite_to_nhm_translations(
+            db_path.to_str().unwrap(),
+            out_path.to_str().unwrap(),
+            false,
+            false,
+            &lang_map,
+            "(Unofficial)",
+        )
+        .unwrap();
+
+        let rebuilt = std::fs::read_to_string(&out_path).unwrap();
+        let rebuilt: serde_json::Value = serde_json::from_str(&rebuilt).unwrap();
+        assert_eq!(rebuilt["Translations"]["Hello"]["es"], "Hola");
+        assert!(rebuilt["Translations"]["Bye"].get("es").is_none());
+
+        let _ = std::fs::remove_file(&translations_path);
+        let _ = std::fs::remove_file(&db_path);
+        let _ = std::fs::remove_file(&out_path);
+    }
+
+    #[test]
+    fn upserting_the_same_translations_path_overwrites_rather_than_duplicates() {
+        let translations_path = temp_path("upsert_in.json");
+        let db_path = temp_path("upsert.db");
+
+        std::fs::write(
+            &translations_path,
+            r#"{"Languages":{"en":"English","es":"Espanol"},"Translations":{"Hello":{"es":"Hola"}}}"#,
+        )
+        .unwrap();
+        let lang_map = LangMap::load(None).unwrap();
+        nhm_translations_to_sqlite(translations_path.to_str().unwrap(), db_path.to_str().unwrap(), &lang_map, "(Unofficial)").unwrap();
+
+        std::fs::write(
+            &translations_path,
+            r#"{"Languages":{"en":"English","es":"Espanol"},"Translations":{"Hello":{"es":"Hola!"}}}"#,
+        )
+        .unwrap();
+        nhm_translations_to_sqlite(translations_path.to_str().unwrap(), db_path.to_str().unwrap(), &lang_map, "(Unofficial)").unwrap();
+
+        let conn = Connection::open(&db_path).unwrap();
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM strings", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 1);
+        let text: String = conn
+            .query_row("SELECT text FROM translations WHERE lang_code = 'es'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(text, "Hola!");
+
+        let _ = std::fs::remove_file(&translations_path);
+        let _ = std::fs::remove_file(&db_path);
+    }
+}
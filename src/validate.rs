@@ -0,0 +1,164 @@
+use std::collections::{BTreeMap, HashMap};
+use anyhow::Result;
+use regex::Regex;
+
+/// A placeholder (`$name`) mismatch found between the English source and a translation.
+#[derive(Debug)]
+pub(crate) struct Mismatch {
+    pub(crate) lang: String,
+    pub(crate) key: String,
+    /// Placeholders present in the English string but missing from the translation.
+    pub(crate) missing: Vec<String>,
+    /// Placeholders present in the translation but absent from the English string.
+    pub(crate) extra: Vec<String>,
+}
+
+fn placeholder_counts(s: &str, re: &Regex) -> HashMap<String, i32> {
+    let mut counts = HashMap::new();
+    for m in re.find_iter(s) {
+        *counts.entry(m.as_str().to_owned()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Compares the placeholder multiset of every translation against the English source sharing
+/// the same crowdin key, returning one `Mismatch` per language/key pair that disagrees.
+pub(crate) fn validate_placeholders(langs: &BTreeMap<String, BTreeMap<String, String>>) -> Vec<Mismatch> {
+    let re = Regex::new(r"\$[a-zA-Z0-9_-]+").unwrap();
+    let mut mismatches = Vec::new();
+
+    let Some(en) = langs.get("en") else {
+        return mismatches;
+    };
+
+    for (key, en_value) in en.iter() {
+        let en_counts = placeholder_counts(en_value, &re);
+
+        for (lang, words) in langs.iter() {
+            if lang == "en" {
+                continue;
+            }
+            let Some(value) = words.get(key) else {
+                continue;
+            };
+            let tr_counts = placeholder_counts(value, &re);
+
+            let mut missing = Vec::new();
+            for (placeholder, &count) in en_counts.iter() {
+                if tr_counts.get(placeholder).copied().unwrap_or(0) < count {
+                    missing.push(placeholder.clone());
+                }
+            }
+            let mut extra = Vec::new();
+            for (placeholder, &count) in tr_counts.iter() {
+                if en_counts.get(placeholder).copied().unwrap_or(0) < count {
+                    extra.push(placeholder.clone());
+                }
+            }
+
+            if !missing.is_empty() || !extra.is_empty() {
+                missing.sort();
+                extra.sort();
+                mismatches.push(Mismatch {
+                    lang: lang.clone(),
+                    key: key.clone(),
+                    missing,
+                    extra,
+                });
+            }
+        }
+    }
+
+    mismatches
+}
+
+/// Runs `validate_placeholders` when `validate` is set, printing any mismatches found and
+/// failing the run unless `validate_warn_only` is also set. Shared by every reverse-direction
+/// format (crowdin, po, mo) so `--validate` behaves the same regardless of `--format`.
+pub(crate) fn check_and_report(
+    langs: &BTreeMap<String, BTreeMap<String, String>>,
+    validate: bool,
+    validate_warn_only: bool,
+) -> Result<()> {
+    if !validate {
+        return Ok(());
+    }
+
+    let mismatches = validate_placeholders(langs);
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+
+    for m in mismatches.iter() {
+        println!(
+            "placeholder mismatch [{}/{}]: missing {:?}, extra {:?}",
+            m.lang, m.key, m.missing, m.extra
+        );
+    }
+    if !validate_warn_only {
+        anyhow::bail!("{} placeholder mismatch(es) found, aborting", mismatches.len());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn langs(pairs: &[(&str, &[(&str, &str)])]) -> BTreeMap<String, BTreeMap<String, String>> {
+        pairs
+            .iter()
+            .map(|(lang, words)| {
+                let words: BTreeMap<String, String> =
+                    words.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+                (lang.to_string(), words)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn flags_a_missing_placeholder() {
+        let langs = langs(&[
+            ("en", &[("greet", "Hi $name")]),
+            ("es", &[("greet", "Hola")]),
+        ]);
+        let mismatches = validate_placeholders(&langs);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].lang, "es");
+        assert_eq!(mismatches[0].missing, vec!["$name".to_string()]);
+        assert!(mismatches[0].extra.is_empty());
+    }
+
+    #[test]
+    fn flags_an_extra_placeholder() {
+        let langs = langs(&[
+            ("en", &[("greet", "Hi")]),
+            ("es", &[("greet", "Hola $name")]),
+        ]);
+        let mismatches = validate_placeholders(&langs);
+        assert_eq!(mismatches.len(), 1);
+        assert!(mismatches[0].missing.is_empty());
+        assert_eq!(mismatches[0].extra, vec!["$name".to_string()]);
+    }
+
+    #[test]
+    fn flags_a_placeholder_occurrence_count_mismatch() {
+        let langs = langs(&[
+            ("en", &[("greet", "$name and $name again")]),
+            ("es", &[("greet", "$name solo una vez")]),
+        ]);
+        let mismatches = validate_placeholders(&langs);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].missing, vec!["$name".to_string()]);
+        assert!(mismatches[0].extra.is_empty());
+    }
+
+    #[test]
+    fn matching_placeholders_produce_no_mismatch() {
+        let langs = langs(&[
+            ("en", &[("greet", "Hi $name")]),
+            ("es", &[("greet", "Hola $name")]),
+        ]);
+        assert!(validate_placeholders(&langs).is_empty());
+    }
+}